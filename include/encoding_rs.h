@@ -453,7 +453,7 @@ uint32_t decoder_decode_to_utf8_without_replacement(
 ///
 /// UB ensues if `decoder` is `NULL`.
 size_t decoder_max_utf16_buffer_length(ENCODING_RS_DECODER const* decoder,
-                                       size_t u16_length);
+                                       size_t byte_length);
 
 /// Incrementally decode a byte stream into UTF-16 with malformed sequences
 /// replaced with the REPLACEMENT CHARACTER.
@@ -556,6 +556,10 @@ bool encoder_has_pending_state(ENCODING_RS_ENCODER const* encoder);
 /// given the current state of the encoder and `byte_length` number of
 /// additional input code units if there are no unmappable characters in
 /// the input or `SIZE_MAX` if `size_t` would overflow.
+///
+/// # Undefined behavior
+///
+/// UB ensues if `encoder` is `NULL`.
 size_t encoder_max_buffer_length_from_utf8_if_no_unmappables(
     ENCODING_RS_ENCODER const* encoder, size_t byte_length);
 
@@ -565,6 +569,10 @@ size_t encoder_max_buffer_length_from_utf8_if_no_unmappables(
 /// Returns the size of the output buffer in bytes that will not overflow
 /// given the current state of the encoder and `byte_length` number of
 /// additional input code units or `SIZE_MAX` if `size_t` would overflow.
+///
+/// # Undefined behavior
+///
+/// UB ensues if `encoder` is `NULL`.
 size_t encoder_max_buffer_length_from_utf8_without_replacement(
     ENCODING_RS_ENCODER const* encoder, size_t byte_length);
 
@@ -627,6 +635,10 @@ uint32_t encoder_encode_from_utf8_without_replacement(
 /// given the current state of the encoder and `u16_length` number of
 /// additional input code units if there are no unmappable characters in
 /// the input or `SIZE_MAX` if `size_t` would overflow.
+///
+/// # Undefined behavior
+///
+/// UB ensues if `encoder` is `NULL`.
 size_t encoder_max_buffer_length_from_utf16_if_no_unmappables(
     ENCODING_RS_ENCODER const* encoder, size_t u16_length);
 
@@ -636,6 +648,10 @@ size_t encoder_max_buffer_length_from_utf16_if_no_unmappables(
 /// Returns the size of the output buffer in bytes that will not overflow
 /// given the current state of the encoder and `u16_length` number of
 /// additional input code units or `SIZE_MAX` if `size_t` would overflow.
+///
+/// # Undefined behavior
+///
+/// UB ensues if `encoder` is `NULL`.
 size_t encoder_max_buffer_length_from_utf16_without_replacement(
     ENCODING_RS_ENCODER const* encoder, size_t u16_length);
 
@@ -646,6 +662,11 @@ size_t encoder_max_buffer_length_from_utf16_without_replacement(
 /// `encoder_encode_*` functions are mapped from Rust and the documentation
 /// for the [`ENCODING_RS_ENCODER`][1] struct for the semantics.
 ///
+/// Unpaired surrogates in the input are treated as U+FFFD REPLACEMENT
+/// CHARACTERS. Therefore, in order for astral characters not to turn into a
+/// pair of REPLACEMENT CHARACTERS, the caller must ensure that surrogate pairs
+/// are not split across input buffer boundaries.
+///
 /// `src` must be non-`NULL` even if `src_len` is zero. When`src_len` is zero,
 /// it is OK for `src` to be something non-dereferencable, such as `0x1`.
 /// Likewise for `dst` when `dst_len` is zero. This is required due to Rust's
@@ -669,6 +690,19 @@ uint32_t encoder_encode_from_utf16(ENCODING_RS_ENCODER* encoder,
 /// `encoder_encode_*` functions are mapped from Rust and the documentation
 /// for the [`ENCODING_RS_ENCODER`][1] struct for the semantics.
 ///
+/// Unpaired surrogates in the input are treated as U+FFFD REPLACEMENT
+/// CHARACTERS. Therefore, in order for astral characters not to turn into a
+/// pair of REPLACEMENT CHARACTERS, the caller must ensure that surrogate pairs
+/// are not split across input buffer boundaries.
+///
+/// An unpaired surrogate is reported as the unmappable value 0xFFFD if the
+/// REPLACEMENT CHARACTER is unmappable in the target encoding. UTF-8 and
+/// gb18030 can encode U+FFFD, so with those targets an unpaired surrogate is
+/// turned into U+FFFD without being reported. Callers that need to reject
+/// unpaired surrogates regardless of the target encoding have to check the
+/// input before encoding, e.g. using `encoding_mem_utf16_valid_up_to()` from
+/// the `encoding_c_mem` crate.
+///
 /// `src` must be non-`NULL` even if `src_len` is zero. When`src_len` is zero,
 /// it is OK for `src` to be something non-dereferencable, such as `0x1`.
 /// Likewise for `dst` when `dst_len` is zero. This is required due to Rust's
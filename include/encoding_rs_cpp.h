@@ -72,11 +72,11 @@ namespace encoding_rs {
  * listed above).
  *
  * Packed info about malformed sequences has the following format:
- * The lowest 8 bits, which can have the decimal value 0, 1, 2 or 3,
- * indicate the number of bytes that were consumed after the malformed
- * sequence and whose next-lowest 8 bits, when shifted right by 8 indicate
- * the length of the malformed byte sequence (possible decimal values 1, 2,
- * 3 or 4). The maximum possible sum of the two is 6.
+ * The lowest 8 bits, which can have the decimal value 1, 2, 3 or 4,
+ * indicate the length of the malformed byte sequence and whose next-lowest
+ * 8 bits, when shifted right by 8 indicate the number of bytes that were
+ * consumed after the malformed sequence (possible decimal values 0, 1, 2
+ * or 3). The maximum possible sum of the two is 6.
  *
  * In the case of methods whose name does not end with
  * `*_without_replacement`, malformed sequences are automatically replaced
@@ -873,20 +873,27 @@ class Encoding final {
    */
   inline std::tuple<std::string, bool> decode_without_bom_handling(
       gsl::span<const uint8_t> bytes) const {
+    size_t valid_up_to = decode_copyable_up_to(bytes);
+    if (valid_up_to == static_cast<size_t>(bytes.size())) {
+      return {std::string(bytes.begin(), bytes.end()), false};
+    }
+    auto tail = bytes.subspan(valid_up_to);
     auto decoder = new_decoder_without_bom_handling();
-    auto needed = decoder->max_utf8_buffer_length(bytes.size());
-    if (!needed) {
+    auto needed = decoder->max_utf8_buffer_length(tail.size());
+    if (!needed || needed.value() > SIZE_MAX - valid_up_to) {
       throw std::overflow_error("Overflow in buffer size computation.");
     }
-    std::string string(needed.value(), '\0');
+    std::string string(bytes.begin(), bytes.begin() + valid_up_to);
+    string.resize(valid_up_to + needed.value());
     const auto [result, read, written, had_errors] = decoder->decode_to_utf8(
-        bytes,
-        gsl::make_span(reinterpret_cast<uint8_t*>(&string[0]), string.size()),
+        tail,
+        gsl::make_span(reinterpret_cast<uint8_t*>(&string[valid_up_to]),
+                       needed.value()),
         true);
-    assert(read == static_cast<size_t>(bytes.size()));
-    assert(written <= static_cast<size_t>(string.size()));
+    assert(read == static_cast<size_t>(tail.size()));
+    assert(written <= needed.value());
     assert(result == INPUT_EMPTY);
-    string.resize(written);
+    string.resize(valid_up_to + written);
     return {string, had_errors};
   }
 
@@ -911,24 +918,30 @@ class Encoding final {
   inline std::optional<std::string>
   decode_without_bom_handling_and_without_replacement(
       gsl::span<const uint8_t> bytes) const {
+    size_t valid_up_to = decode_copyable_up_to(bytes);
+    if (valid_up_to == static_cast<size_t>(bytes.size())) {
+      return std::string(bytes.begin(), bytes.end());
+    }
+    auto tail = bytes.subspan(valid_up_to);
     auto decoder = new_decoder_without_bom_handling();
     auto needed =
-        decoder->max_utf8_buffer_length_without_replacement(bytes.size());
-    if (!needed) {
+        decoder->max_utf8_buffer_length_without_replacement(tail.size());
+    if (!needed || needed.value() > SIZE_MAX - valid_up_to) {
       throw std::overflow_error("Overflow in buffer size computation.");
     }
-    std::string string(needed.value(), '\0');
+    std::string string(bytes.begin(), bytes.begin() + valid_up_to);
+    string.resize(valid_up_to + needed.value());
     const auto [result, read, written] =
         decoder->decode_to_utf8_without_replacement(
-            bytes,
-            gsl::make_span(reinterpret_cast<uint8_t*>(&string[0]),
-                           string.size()),
+            tail,
+            gsl::make_span(reinterpret_cast<uint8_t*>(&string[valid_up_to]),
+                           needed.value()),
             true);
     assert(result != OUTPUT_FULL);
     if (result == INPUT_EMPTY) {
-      assert(read == static_cast<size_t>(bytes.size()));
-      assert(written <= static_cast<size_t>(string.size()));
-      string.resize(written);
+      assert(read == static_cast<size_t>(tail.size()));
+      assert(written <= needed.value());
+      string.resize(valid_up_to + written);
       return string;
     }
     return std::nullopt;
@@ -1102,19 +1115,25 @@ class Encoding final {
   encode(std::string_view string) const {
     auto output_enc = output_encoding();
     if (output_enc == UTF_8_ENCODING) {
-      std::vector<uint8_t> vec(string.size());
-      std::memcpy(&vec[0], string.data(), string.size());
+      return {std::vector<uint8_t>(string.begin(), string.end()),
+              gsl::not_null<const Encoding*>(output_enc), false};
+    }
+    size_t valid_up_to = output_enc->encode_copyable_up_to(string);
+    if (valid_up_to == static_cast<size_t>(string.size())) {
+      return {std::vector<uint8_t>(string.begin(), string.end()),
+              gsl::not_null<const Encoding*>(output_enc), false};
     }
     auto encoder = output_enc->new_encoder();
-    auto needed =
-        encoder->max_buffer_length_from_utf8_if_no_unmappables(string.size());
-    if (!needed) {
+    auto needed = encoder->max_buffer_length_from_utf8_if_no_unmappables(
+        string.size() - valid_up_to);
+    if (!needed || needed.value() > SIZE_MAX - valid_up_to) {
       throw std::overflow_error("Overflow in buffer size computation.");
     }
-    std::vector<uint8_t> vec(needed.value());
+    std::vector<uint8_t> vec(string.begin(), string.begin() + valid_up_to);
+    vec.resize(valid_up_to + needed.value());
     bool total_had_errors = false;
-    size_t total_read = 0;
-    size_t total_written = 0;
+    size_t total_read = valid_up_to;
+    size_t total_written = valid_up_to;
     for (;;) {
       const auto [result, read, written, had_errors] =
           encoder->encode_from_utf8(string.substr(total_read),
@@ -1340,6 +1359,38 @@ class Encoding final {
     return ptr ? ptr : reinterpret_cast<T*>(alignof(T));
   }
 
+  /**
+   * Returns the length of the prefix of `bytes` whose decoding to UTF-8
+   * without BOM handling is identical to the input bytes.
+   */
+  inline size_t decode_copyable_up_to(gsl::span<const uint8_t> bytes) const {
+    if (this == UTF_8_ENCODING) {
+      return utf8_valid_up_to(bytes);
+    }
+    if (this == ISO_2022_JP_ENCODING) {
+      return iso_2022_jp_ascii_valid_up_to(bytes);
+    }
+    if (is_ascii_compatible()) {
+      return ascii_valid_up_to(bytes);
+    }
+    return 0;
+  }
+
+  /**
+   * Returns the length of the prefix of `string` whose encoding with this
+   * encoding is identical to the input bytes. This must only be called on
+   * an output encoding other than `UTF_8_ENCODING`.
+   */
+  inline size_t encode_copyable_up_to(std::string_view string) const {
+    assert(this != UTF_8_ENCODING);
+    auto bytes = gsl::make_span(reinterpret_cast<const uint8_t*>(string.data()),
+                                string.size());
+    if (this == ISO_2022_JP_ENCODING) {
+      return iso_2022_jp_ascii_valid_up_to(bytes);
+    }
+    return ascii_valid_up_to(bytes);
+  }
+
   Encoding() = delete;
   Encoding(const Encoding&) = delete;
   Encoding& operator=(const Encoding&) = delete;
@@ -39,10 +39,10 @@
 //! `InputEmpty` becomes `INPUT_EMPTY`. `OutputFull` becomes `OUTPUT_FULL`.
 //! `Unmappable` becomes the scalar value of the unmappable character.
 //! `Malformed` becomes a number whose lowest 8 bits, which can have the decimal
-//! value 0, 1, 2 or 3, indicate the number of bytes that were consumed after
-//! the malformed sequence and whose next-lowest 8 bits, when shifted right by
-//! 8 indicate the length of the malformed byte sequence (possible decimal
-//! values 1, 2, 3 or 4). The maximum possible sum of the two is 6.
+//! value 1, 2, 3 or 4, indicate the length of the malformed byte sequence and
+//! whose next-lowest 8 bits, when shifted right by 8 indicate the number of
+//! bytes that were consumed after the malformed sequence (possible decimal
+//! values 0, 1, 2 or 3). The maximum possible sum of the two is 6.
 
 extern crate encoding_rs;
 
@@ -827,10 +827,10 @@ pub unsafe extern "C" fn decoder_decode_to_utf8_without_replacement(
 #[no_mangle]
 pub unsafe extern "C" fn decoder_max_utf16_buffer_length(
     decoder: *const Decoder,
-    u16_length: usize,
+    byte_length: usize,
 ) -> usize {
     (*decoder)
-        .max_utf16_buffer_length(u16_length)
+        .max_utf16_buffer_length(byte_length)
         .unwrap_or(::std::usize::MAX)
 }
 
@@ -977,6 +977,10 @@ pub unsafe extern "C" fn encoder_has_pending_state(encoder: *const Encoder) -> b
 /// given the current state of the encoder and `byte_length` number of
 /// additional input code units if there are no unmappable characters in
 /// the input or `SIZE_MAX` if `size_t` would overflow.
+///
+/// # Undefined behavior
+///
+/// UB ensues if `encoder` is `NULL`.
 #[no_mangle]
 pub unsafe extern "C" fn encoder_max_buffer_length_from_utf8_if_no_unmappables(
     encoder: *const Encoder,
@@ -993,6 +997,10 @@ pub unsafe extern "C" fn encoder_max_buffer_length_from_utf8_if_no_unmappables(
 /// Returns the size of the output buffer in bytes that will not overflow
 /// given the current state of the encoder and `byte_length` number of
 /// additional input code units or `SIZE_MAX` if `size_t` would overflow.
+///
+/// # Undefined behavior
+///
+/// UB ensues if `encoder` is `NULL`.
 #[no_mangle]
 pub unsafe extern "C" fn encoder_max_buffer_length_from_utf8_without_replacement(
     encoder: *const Encoder,
@@ -1092,6 +1100,10 @@ pub unsafe extern "C" fn encoder_encode_from_utf8_without_replacement(
 /// given the current state of the encoder and `u16_length` number of
 /// additional input code units if there are no unmappable characters in
 /// the input or `SIZE_MAX` if `size_t` would overflow.
+///
+/// # Undefined behavior
+///
+/// UB ensues if `encoder` is `NULL`.
 #[no_mangle]
 pub unsafe extern "C" fn encoder_max_buffer_length_from_utf16_if_no_unmappables(
     encoder: *const Encoder,
@@ -1108,6 +1120,10 @@ pub unsafe extern "C" fn encoder_max_buffer_length_from_utf16_if_no_unmappables(
 /// Returns the size of the output buffer in bytes that will not overflow
 /// given the current state of the encoder and `u16_length` number of
 /// additional input code units or `SIZE_MAX` if `size_t` would overflow.
+///
+/// # Undefined behavior
+///
+/// UB ensues if `encoder` is `NULL`.
 #[no_mangle]
 pub unsafe extern "C" fn encoder_max_buffer_length_from_utf16_without_replacement(
     encoder: *const Encoder,
@@ -1125,6 +1141,11 @@ pub unsafe extern "C" fn encoder_max_buffer_length_from_utf16_without_replacemen
 /// `encoder_encode_*` functions are mapped from Rust and the documentation
 /// for the [`Encoder`][1] struct for the semantics.
 ///
+/// Unpaired surrogates in the input are treated as U+FFFD REPLACEMENT
+/// CHARACTERS. Therefore, in order for astral characters not to turn into a
+/// pair of REPLACEMENT CHARACTERS, the caller must ensure that surrogate pairs
+/// are not split across input buffer boundaries.
+///
 /// `src` must be non-`NULL` even if `src_len` is zero. When`src_len` is zero,
 /// it is OK for `src` to be something non-dereferencable, such as `0x1`.
 /// Likewise for `dst` when `dst_len` is zero. This is required due to Rust's
@@ -1163,6 +1184,19 @@ pub unsafe extern "C" fn encoder_encode_from_utf16(
 /// `encoder_encode_*` functions are mapped from Rust and the documentation
 /// for the [`Encoder`][1] struct for the semantics.
 ///
+/// Unpaired surrogates in the input are treated as U+FFFD REPLACEMENT
+/// CHARACTERS. Therefore, in order for astral characters not to turn into a
+/// pair of REPLACEMENT CHARACTERS, the caller must ensure that surrogate pairs
+/// are not split across input buffer boundaries.
+///
+/// An unpaired surrogate is reported as the unmappable value 0xFFFD if the
+/// REPLACEMENT CHARACTER is unmappable in the target encoding. UTF-8 and
+/// gb18030 can encode U+FFFD, so with those targets an unpaired surrogate is
+/// turned into U+FFFD without being reported. Callers that need to reject
+/// unpaired surrogates regardless of the target encoding have to check the
+/// input before encoding, e.g. using `encoding_mem_utf16_valid_up_to()` from
+/// the `encoding_c_mem` crate.
+///
 /// `src` must be non-`NULL` even if `src_len` is zero. When`src_len` is zero,
 /// it is OK for `src` to be something non-dereferencable, such as `0x1`.
 /// Likewise for `dst` when `dst_len` is zero. This is required due to Rust's